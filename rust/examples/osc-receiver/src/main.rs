@@ -27,44 +27,12 @@ use std::net::UdpSocket;
 use std::time::{Duration, Instant};
 
 use rosc::{OscMessage, OscPacket, OscType};
-use scalatrix::Mos;
+use scalatrix::PitchGridParams;
 
 /// Default ports matching the PitchGrid plugin's OSC configuration.
 const RECEIVE_PORT: u16 = 34561; // We listen here (plugin sends to this port)
 const PLUGIN_PORT: u16 = 34562;  // Plugin listens here (we send heartbeats here)
 
-/// Parsed tuning/mapping parameters from an OSC message.
-#[derive(Debug, Clone)]
-struct PitchGridParams {
-    mode: i32,
-    root_freq: f64,
-    stretch: f64,
-    skew: f64,
-    mode_offset: i32,
-    steps: i32,
-    mos_a: i32,
-    mos_b: i32,
-}
-
-impl PitchGridParams {
-    /// Try to parse from OSC args: (i32, f32, f32, f32, i32, i32, i32, i32)
-    fn from_osc_args(args: &[OscType]) -> Option<Self> {
-        if args.len() < 8 {
-            return None;
-        }
-        Some(Self {
-            mode:        args[0].clone().int()?,
-            root_freq:   args[1].clone().float()? as f64,
-            stretch:     args[2].clone().float()? as f64,
-            skew:        args[3].clone().float()? as f64,
-            mode_offset: args[4].clone().int()?,
-            steps:       args[5].clone().int()?,
-            mos_a:       args[6].clone().int()?,
-            mos_b:       args[7].clone().int()?,
-        })
-    }
-}
-
 /// Send a heartbeat message to the PitchGrid plugin.
 fn send_heartbeat(socket: &UdpSocket) {
     let msg = OscMessage {
@@ -78,14 +46,7 @@ fn send_heartbeat(socket: &UdpSocket) {
 /// Process mapping parameters: create MOS and generate scale.
 fn process_mapping(params: &PitchGridParams) {
     // Create a MOS from the received parameters
-    let mos = Mos::from_params(
-        params.mos_a,
-        params.mos_b,
-        params.mode,
-        params.stretch,    // equave
-        params.skew,       // generator
-        1,                 // repetitions
-    );
+    let mos = params.to_mos();
 
     println!("  MOS: {mos} — ({}, {}) n={}", mos.a(), mos.b(), mos.n());
     println!("  Generator: {:.6}, Equave: {:.6}", mos.generator(), mos.equave());
@@ -93,13 +54,7 @@ fn process_mapping(params: &PitchGridParams) {
         mos.large_step_ratio(), mos.small_step_ratio(), mos.chroma_ratio());
 
     // Generate the MIDI-mapped scale
-    let scale = mos.generate_mapped_scale(
-        params.steps,
-        params.mode_offset as f64,
-        params.root_freq,
-        128,  // MIDI range
-        60,   // root = middle C
-    );
+    let scale = params.to_scale();
 
     println!("  Scale: {} nodes, root at index {}", scale.len(), scale.root_idx());
 
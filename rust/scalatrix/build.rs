@@ -0,0 +1,17 @@
+//! Compiles `proto/tuning.proto` with `protobuf-codegen-pure` — a pure
+//! Rust parser/codegen that doesn't shell out to a `protoc` binary,
+//! unlike the `cc` build in `scalatrix-sys` which does need a system
+//! C++ toolchain.
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+
+    protobuf_codegen_pure::Codegen::new()
+        .out_dir(&out_dir)
+        .include("proto")
+        .input("proto/tuning.proto")
+        .run()
+        .expect("failed to compile proto/tuning.proto");
+
+    println!("cargo:rerun-if-changed=proto/tuning.proto");
+}
@@ -0,0 +1,3 @@
+//! Bridges from PitchGrid's native OSC transport to other protocols.
+
+pub mod mqtt;
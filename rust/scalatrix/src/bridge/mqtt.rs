@@ -0,0 +1,121 @@
+//! Publishes and subscribes to MOS tuning state over an MQTT broker.
+//!
+//! UDP heartbeats only reach whoever is already listening; a synth that
+//! connects after the last tuning change never sees it. Retained MQTT
+//! messages fix that: a late subscriber gets the last-known tuning the
+//! moment it subscribes, with no need to wait for the next change.
+
+use rumqttc::{Client, Connection, Event, LastWill, MqttOptions, Packet, QoS};
+
+use crate::{PitchGridParams, Scale};
+
+const TUNING_TOPIC: &str = "pitchgrid/tuning";
+
+/// Connection settings for [`MqttBridge::connect`].
+pub struct MqttConfig {
+    pub broker_host: String,
+    pub broker_port: u16,
+    pub client_id: String,
+    pub qos: QoS,
+    /// Topic announcing connection status; carries a retained
+    /// "offline" last-will payload so downstream apps can detect a
+    /// dropped tuning source.
+    pub status_topic: String,
+}
+
+impl MqttConfig {
+    pub fn new(broker_host: impl Into<String>, broker_port: u16, client_id: impl Into<String>) -> Self {
+        Self {
+            broker_host: broker_host.into(),
+            broker_port,
+            client_id: client_id.into(),
+            qos: QoS::AtLeastOnce,
+            status_topic: format!("{TUNING_TOPIC}/status"),
+        }
+    }
+}
+
+/// An MQTT connection carrying [`PitchGridParams`] tuning state.
+pub struct MqttBridge {
+    client: Client,
+    connection: Connection,
+    qos: QoS,
+}
+
+impl MqttBridge {
+    /// Connect to the broker, registering a retained last-will message
+    /// on `config.status_topic` so subscribers learn when this source
+    /// disconnects.
+    ///
+    /// The retained "online" message queued here, and every
+    /// subsequent [`MqttBridge::publish_tuning`], only reaches the
+    /// broker once the connection is driven — see [`MqttBridge::poll`].
+    pub fn connect(config: MqttConfig) -> Result<Self, rumqttc::ClientError> {
+        let mut options = MqttOptions::new(config.client_id, config.broker_host, config.broker_port);
+        options.set_last_will(LastWill::new(&config.status_topic, "offline", config.qos, true));
+
+        let (client, connection) = Client::new(options, 10);
+        client.publish(&config.status_topic, config.qos, true, "online")?;
+
+        Ok(Self { client, connection, qos: config.qos })
+    }
+
+    /// Publish the current tuning: each field under its own
+    /// `pitchgrid/tuning/<field>` topic, plus a single retained JSON
+    /// payload on `pitchgrid/tuning` for consumers that want the whole
+    /// struct at once.
+    pub fn publish_tuning(&self, params: &PitchGridParams) -> Result<(), rumqttc::ClientError> {
+        self.client.publish(format!("{TUNING_TOPIC}/mode"), self.qos, true, params.mode.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/root_freq"), self.qos, true, params.root_freq.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/stretch"), self.qos, true, params.stretch.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/skew"), self.qos, true, params.skew.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/mode_offset"), self.qos, true, params.mode_offset.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/steps"), self.qos, true, params.steps.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/mos_a"), self.qos, true, params.mos_a.to_string())?;
+        self.client.publish(format!("{TUNING_TOPIC}/mos_b"), self.qos, true, params.mos_b.to_string())?;
+
+        let json = serde_json::to_vec(params).expect("PitchGridParams always serializes");
+        self.client.publish(TUNING_TOPIC, self.qos, true, json)?;
+        Ok(())
+    }
+
+    /// Drive the underlying MQTT event loop by one notification.
+    ///
+    /// `rumqttc`'s sync [`Client`] only queues requests (publishes,
+    /// subscribes, the `connect()`-time "online" message); nothing is
+    /// actually written to the broker until [`Connection`] is polled.
+    /// A source that only calls [`MqttBridge::publish_tuning`] and
+    /// never [`MqttBridge::subscribe_tuning`] must still drive the
+    /// connection somehow, or the request channel fills and `publish`
+    /// blocks forever — call `poll` in a loop (e.g. on a background
+    /// thread, or once per publish) to flush it.
+    pub fn poll(&mut self) -> Option<Result<Event, rumqttc::ConnectionError>> {
+        self.connection.iter().next()
+    }
+
+    /// Subscribe to `pitchgrid/tuning` and run the mapping pipeline
+    /// (`Mos::from_params` → `generate_mapped_scale`) on every retained
+    /// or live update, delivering the resulting scale to `on_scale`.
+    ///
+    /// Blocks, driving the underlying MQTT event loop, until the
+    /// connection ends.
+    pub fn subscribe_tuning(
+        &mut self,
+        mut on_scale: impl FnMut(PitchGridParams, Scale),
+    ) -> Result<(), rumqttc::ConnectionError> {
+        // If the event loop channel is already gone, the connection
+        // iterator below will observe it too.
+        let _ = self.client.subscribe(TUNING_TOPIC, self.qos);
+
+        for notification in self.connection.iter() {
+            let Event::Incoming(Packet::Publish(publish)) = notification? else {
+                continue;
+            };
+            if let Ok(params) = serde_json::from_slice::<PitchGridParams>(&publish.payload) {
+                let scale = params.to_scale();
+                on_scale(params, scale);
+            }
+        }
+        Ok(())
+    }
+}
@@ -23,6 +23,18 @@
 
 use scalatrix_sys as ffi;
 
+mod quantizer;
+pub use quantizer::Quantizer;
+
+pub mod osc;
+
+mod params;
+pub use params::PitchGridParams;
+
+pub mod bridge;
+
+pub mod wire;
+
 /// Integer 2D vector representing lattice coordinates.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Vec2i {
@@ -72,6 +84,26 @@ pub struct Node {
     pub pitch: f64,
 }
 
+/// Summary of a single scale within a generator's MOS family.
+///
+/// Returned by [`Mos::family`], which walks the successive MOS scales
+/// produced by a fixed generator in order of increasing note count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MosInfo {
+    /// Count of large intervals.
+    pub a: i32,
+    /// Count of small intervals.
+    pub b: i32,
+    /// Total scale size (a + b).
+    pub n: i32,
+    /// Large step size in cents.
+    pub large_cents: f64,
+    /// Small step size in cents.
+    pub small_cents: f64,
+    /// Chroma (large − small) in cents.
+    pub chroma_cents: f64,
+}
+
 /// Moment of Symmetry scale system.
 ///
 /// Defines a generalized diatonic scale on a 2D lattice, parameterized by:
@@ -238,6 +270,137 @@ impl Mos {
     }
 }
 
+/// The modular inverse of `value` mod `modulus`, via the extended
+/// Euclidean algorithm. Used by [`Mos::generator_bounds`]; callers must
+/// ensure `gcd(value, modulus) == 1` and `modulus > 1`.
+fn mod_inverse(value: i64, modulus: i64) -> i64 {
+    let (mut old_r, mut r) = (modulus, value.rem_euclid(modulus));
+    let (mut old_t, mut t) = (0i64, 1i64);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+    old_t.rem_euclid(modulus)
+}
+
+impl Mos {
+    /// Enumerate the MOS scales produced by a generator, in order of
+    /// increasing note count, via the three-gap theorem.
+    ///
+    /// For each candidate size `n`, the points `{k·generator mod 1 : k =
+    /// 0..n-1}` (plus the wrap-around point `1.0`) are sorted and their
+    /// gaps measured. Exactly two distinct gap sizes means the n-note
+    /// scale is a MOS; three means it isn't and `n` is skipped. The walk
+    /// stops once the chroma (`L − s`) falls below `chroma_threshold`
+    /// cents, at which point the generator's best approximation has been
+    /// reached and the scale is treated as equal-step.
+    ///
+    /// This is a pure-Rust enumeration — it doesn't require an `(a, b)`
+    /// pair up front, unlike [`Mos::from_params`].
+    pub fn family(generator: f64, equave: f64, repetitions: i32, chroma_threshold: f64) -> Vec<MosInfo> {
+        const EPS: f64 = 1e-9;
+        const MAX_N: i32 = 1000;
+
+        let period = equave / repetitions as f64;
+        let g = generator.rem_euclid(1.0);
+
+        let mut family = Vec::new();
+        let mut n = 2;
+        while n <= MAX_N {
+            let mut points: Vec<f64> = (0..n).map(|k| (k as f64 * g).rem_euclid(1.0)).collect();
+            points.push(1.0);
+            points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let gaps: Vec<f64> = points.windows(2).map(|w| w[1] - w[0]).collect();
+            let mut sorted_gaps = gaps.clone();
+            sorted_gaps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mut distinct: Vec<f64> = Vec::new();
+            for &gap in &sorted_gaps {
+                if distinct.last().map_or(true, |&last| (gap - last).abs() > EPS) {
+                    distinct.push(gap);
+                }
+            }
+
+            if distinct.len() > 2 {
+                // Non-MOS size — skip and keep walking.
+                n += 1;
+                continue;
+            }
+
+            let (small_gap, large_gap) = if distinct.len() == 1 {
+                (distinct[0], distinct[0])
+            } else {
+                (distinct[0], distinct[1])
+            };
+            let b = gaps.iter().filter(|&&gap| (gap - small_gap).abs() <= EPS).count() as i32;
+            let a = n - b;
+
+            let large_cents = large_gap * period * 1200.0;
+            let small_cents = small_gap * period * 1200.0;
+            let chroma_cents = large_cents - small_cents;
+
+            family.push(MosInfo { a, b, n, large_cents, small_cents, chroma_cents });
+
+            if chroma_cents < chroma_threshold {
+                break;
+            }
+            n += 1;
+        }
+        family
+    }
+
+    /// Query the generator interval (inclusive, as a fraction of the
+    /// period) that produces the given `a` large / `b` small MOS
+    /// structure.
+    ///
+    /// This is the inverse of [`Mos::family`]: the valid generators for
+    /// an `aLbs` scale are bounded by the two equal-tempered generators
+    /// that neighbour it — the bright limit `p/a0`, where stacking `a0`
+    /// generators divides the period into `a0` equal (large) steps and
+    /// the small step has degenerated to zero, and the dark limit
+    /// `q/n0`, where `L = s` and the whole period divides evenly into
+    /// `n0` steps. `p` is the modular inverse of `b0` mod `a0` (the
+    /// unique numerator in `[1, a0)` making `p/a0` and `q/n0` Farey
+    /// neighbours, i.e. `|p*n0 - q*a0| = 1`), and `q` follows from `p`
+    /// by the same identity.
+    pub fn generator_bounds(a: i32, b: i32, equave: f64, repetitions: i32) -> (f64, f64) {
+        debug_assert!(equave > 0.0);
+        debug_assert!(repetitions > 0);
+
+        let a0 = (a / repetitions) as i64;
+        let b0 = (b / repetitions) as i64;
+        let n0 = a0 + b0;
+
+        if a0 == 0 || b0 == 0 {
+            // Degenerate structure (no chroma to bound, or a single
+            // large step spanning the whole period) — the generator is
+            // unconstrained.
+            return (0.0, 1.0);
+        }
+
+        if a0 == 1 {
+            // `b0` has no inverse mod 1; a `1Lbs` scale is bracketed by
+            // the Farey neighbours 0/1 and 1/n0.
+            return (0.0, 1.0 / n0 as f64);
+        }
+
+        let p = mod_inverse(b0, a0);
+        let m = (p * b0 - 1) / a0;
+        let q = p + m;
+
+        let bright = p as f64 / a0 as f64;
+        let dark = q as f64 / n0 as f64;
+        if dark <= bright { (dark, bright) } else { (bright, dark) }
+    }
+
+    /// Convert a generator value (as a fraction of the period, such as
+    /// one of the bounds returned by [`Mos::generator_bounds`]) to cents.
+    pub fn generator_to_cents(generator: f64, equave: f64, repetitions: i32) -> f64 {
+        generator * (equave / repetitions as f64) * 1200.0
+    }
+}
+
 impl std::fmt::Debug for Mos {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Mos")
@@ -325,6 +488,132 @@ impl Scale {
         }
         map
     }
+
+    /// Build a [`Quantizer`] over this scale's nodes for real-time pitch
+    /// snapping.
+    pub fn quantizer(&self) -> Quantizer {
+        Quantizer::new(self)
+    }
+
+    /// Cents of each degree above the root, up to and including the
+    /// equave. Scans forward from `root_idx()` until the pitch ratio
+    /// reaches `equave` (the log2 frequency ratio of the interval of
+    /// equivalence the scale was generated with — 1.0 for an octave,
+    /// but PitchGrid scales can be stretched to a non-octave equave),
+    /// which is the shared building block for [`Scale::to_scala_scl`]
+    /// and [`Scale::to_scala_kbm`].
+    fn period_degrees_cents(&self, equave: f64) -> Vec<f64> {
+        let period_ratio = 2f64.powf(equave);
+        let base_freq = self.base_freq();
+        let nodes = self.nodes();
+        let mut degrees = Vec::new();
+        for node in nodes.iter().skip(self.root_idx() + 1) {
+            let ratio = node.pitch / base_freq;
+            if ratio <= 0.0 {
+                continue;
+            }
+            degrees.push(1200.0 * ratio.log2());
+            if ratio >= period_ratio - 1e-9 {
+                break;
+            }
+        }
+        degrees
+    }
+
+    /// Export this scale as a Scala `.scl` tuning file: a description
+    /// line, the interval count, then one cents value per step from the
+    /// root up to and including the equave.
+    ///
+    /// `equave` is the log2 frequency ratio of the interval of
+    /// equivalence the scale was generated with (see
+    /// [`Mos::equave`]) — 1.0 for an octave.
+    pub fn to_scala_scl(&self, equave: f64) -> String {
+        let degrees = self.period_degrees_cents(equave);
+
+        let mut scl = String::new();
+        scl.push_str("! Exported by scalatrix\n");
+        scl.push_str("!\n");
+        scl.push_str("scalatrix scale\n");
+        scl.push_str(&format!(" {}\n", degrees.len()));
+        scl.push_str("!\n");
+        for cents in &degrees {
+            scl.push_str(&format!(" {cents:.6}\n"));
+        }
+        scl
+    }
+
+    /// Export this scale as a Scala `.kbm` keyboard map, linking MIDI key
+    /// numbers 1:1 to scale degrees with `root_idx()` and `base_freq()`
+    /// as the reference key/frequency.
+    ///
+    /// `equave` is the log2 frequency ratio of the interval of
+    /// equivalence the scale was generated with (see
+    /// [`Mos::equave`]) — 1.0 for an octave.
+    pub fn to_scala_kbm(&self, equave: f64) -> String {
+        let map_size = self.period_degrees_cents(equave).len();
+        let root = self.root_idx();
+        let last_key = self.len().saturating_sub(1);
+
+        let mut kbm = String::new();
+        kbm.push_str("! Exported by scalatrix\n");
+        kbm.push_str(&format!("{map_size}\n"));
+        kbm.push_str("0\n");
+        kbm.push_str(&format!("{last_key}\n"));
+        kbm.push_str(&format!("{root}\n"));
+        kbm.push_str(&format!("{root}\n"));
+        kbm.push_str(&format!("{:.6}\n", self.base_freq()));
+        kbm.push_str(&format!("{map_size}\n"));
+        for degree in 0..map_size {
+            kbm.push_str(&format!("{degree}\n"));
+        }
+        kbm
+    }
+
+    /// Export this scale as a MIDI Tuning Standard non-real-time bulk
+    /// dump SysEx message covering all 128 MIDI notes.
+    ///
+    /// Each note entry is the nearest 12-TET semitone at or below the
+    /// node's pitch, plus a 14-bit fraction of a semitone above it.
+    pub fn to_mts_bulk_dump(&self, device_id: u8, program: u8) -> Vec<u8> {
+        let mut body = Vec::with_capacity(5 + 16 + 128 * 3);
+        body.push(0x7E); // non-real-time
+        body.push(device_id & 0x7F);
+        body.push(0x08); // sub-id: MIDI tuning standard
+        body.push(0x01); // sub-id2: bulk dump reply
+        body.push(program & 0x7F);
+
+        let mut name = [b' '; 16];
+        for (slot, byte) in name.iter_mut().zip(b"scalatrix".iter()) {
+            *slot = *byte;
+        }
+        body.extend_from_slice(&name);
+
+        for i in 0..128 {
+            let pitch = self.node(i).map(|n| n.pitch).filter(|&p| p > 0.0);
+            let (key, msb, lsb) = match pitch {
+                Some(pitch) => {
+                    let note_number = 69.0 + 12.0 * (pitch / 440.0).log2();
+                    let key = note_number.floor().clamp(0.0, 126.0);
+                    let frac = note_number - key;
+                    let frac14 = (frac * 16384.0).round().clamp(0.0, 16383.0) as u16;
+                    (key as u8, ((frac14 >> 7) & 0x7F) as u8, (frac14 & 0x7F) as u8)
+                }
+                None => (0, 0, 0),
+            };
+            body.push(key);
+            body.push(msb);
+            body.push(lsb);
+        }
+
+        let checksum = body.iter().fold(0u8, |acc, b| acc ^ b) & 0x7F;
+
+        let mut dump = Vec::with_capacity(body.len() + 3);
+        dump.push(0xF0);
+        dump.extend(body);
+        dump.push(checksum);
+        dump.push(0xF7);
+        dump
+    }
 }
 
 impl std::fmt::Debug for Scale {
@@ -336,3 +625,31 @@ impl std::fmt::Debug for Scale {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `Mos::family` and `Mos::generator_bounds` are inverses of each
+    /// other: for a generator known to produce an `aLbs` MOS, the
+    /// generator must fall within `generator_bounds(a, b, ...)`, and
+    /// `family(generator, ...)` must in turn report that `(a, b)`.
+    #[test]
+    fn generator_bounds_matches_family() {
+        let cases = [(5, 2, 0.583_333), (2, 1, 0.4), (3, 4, 0.3)];
+
+        for (a, b, generator) in cases {
+            let (lo, hi) = Mos::generator_bounds(a, b, 1.0, 1);
+            assert!(
+                lo <= generator && generator <= hi,
+                "{a}L{b}s: generator {generator} not in bounds ({lo}, {hi})"
+            );
+
+            let family = Mos::family(generator, 1.0, 1, 1.0);
+            assert!(
+                family.iter().any(|info| info.a == a && info.b == b),
+                "{a}L{b}s: family({generator}) didn't contain (a={a}, b={b}): {family:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Network configuration for the OSC transport, loaded in layers
+//! (defaults → config file → env vars → CLI args) so the receiver and
+//! the plugin can run on different machines instead of only
+//! `127.0.0.1`.
+
+use std::io::{self, Write};
+use std::net::{SocketAddr, UdpSocket};
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_RECEIVE_ADDR: &str = "127.0.0.1:34561";
+const DEFAULT_SEND_ADDR: &str = "127.0.0.1:34562";
+const DEFAULT_HEARTBEAT_MS: u64 = 1000;
+
+/// Network configuration for an OSC receiver/sender pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Address this process binds its receive socket to.
+    pub receive_addr: SocketAddr,
+    /// Address heartbeats and replies are sent to.
+    pub send_addr: SocketAddr,
+    /// How often to send a heartbeat.
+    pub heartbeat_interval: Duration,
+    /// Externally reachable address to announce in heartbeats, when it
+    /// can't be inferred from `receive_addr` — e.g. behind NAT, or when
+    /// bound to `0.0.0.0`. Falls back to `receive_addr` when unset.
+    pub advertise_addr: Option<SocketAddr>,
+}
+
+impl Config {
+    /// Defaults matching the original hardcoded same-host example.
+    pub fn defaults() -> Self {
+        Self {
+            receive_addr: DEFAULT_RECEIVE_ADDR.parse().unwrap(),
+            send_addr: DEFAULT_SEND_ADDR.parse().unwrap(),
+            heartbeat_interval: Duration::from_millis(DEFAULT_HEARTBEAT_MS),
+            advertise_addr: None,
+        }
+    }
+
+    /// The address to announce in heartbeats: `advertise_addr` if set,
+    /// otherwise `receive_addr`.
+    pub fn advertised_addr(&self) -> SocketAddr {
+        self.advertise_addr.unwrap_or(self.receive_addr)
+    }
+
+    /// Load configuration by layering, in increasing priority: built-in
+    /// defaults, an optional config file, environment variables
+    /// (`SCALATRIX_RECEIVE_ADDR`, `SCALATRIX_SEND_ADDR`,
+    /// `SCALATRIX_HEARTBEAT_MS`, `SCALATRIX_ADVERTISE_ADDR`), then CLI
+    /// args (`--receive-addr`, `--send-addr`, `--heartbeat-ms`,
+    /// `--advertise-addr`).
+    pub fn load(config_path: Option<&Path>, args: impl Iterator<Item = String>) -> io::Result<Self> {
+        let mut config = Self::defaults();
+        if let Some(path) = config_path {
+            if path.exists() {
+                config.merge_file(path)?;
+            }
+        }
+        config.merge_env();
+        config.merge_args(args);
+        Ok(config)
+    }
+
+    fn merge_file(&mut self, path: &Path) -> io::Result<()> {
+        let contents = std::fs::read_to_string(path)?;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                self.set_field(key.trim(), value.trim());
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_env(&mut self) {
+        for (env_key, field) in [
+            ("SCALATRIX_RECEIVE_ADDR", "receive_addr"),
+            ("SCALATRIX_SEND_ADDR", "send_addr"),
+            ("SCALATRIX_HEARTBEAT_MS", "heartbeat_ms"),
+            ("SCALATRIX_ADVERTISE_ADDR", "advertise_addr"),
+        ] {
+            if let Ok(value) = std::env::var(env_key) {
+                self.set_field(field, &value);
+            }
+        }
+    }
+
+    fn merge_args(&mut self, args: impl Iterator<Item = String>) {
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            let field = match arg.as_str() {
+                "--receive-addr" => "receive_addr",
+                "--send-addr" => "send_addr",
+                "--heartbeat-ms" => "heartbeat_ms",
+                "--advertise-addr" => "advertise_addr",
+                _ => continue,
+            };
+            if let Some(value) = args.next() {
+                self.set_field(field, &value);
+            }
+        }
+    }
+
+    fn set_field(&mut self, field: &str, value: &str) {
+        match field {
+            "receive_addr" => {
+                if let Ok(addr) = value.parse() {
+                    self.receive_addr = addr;
+                }
+            }
+            "send_addr" => {
+                if let Ok(addr) = value.parse() {
+                    self.send_addr = addr;
+                }
+            }
+            "heartbeat_ms" => {
+                if let Ok(ms) = value.parse() {
+                    self.heartbeat_interval = Duration::from_millis(ms);
+                }
+            }
+            "advertise_addr" => {
+                if let Ok(addr) = value.parse() {
+                    self.advertise_addr = Some(addr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Write this configuration to a file in the same `key=value`
+    /// format [`Config::load`] reads back.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::new();
+        out.push_str(&format!("receive_addr={}\n", self.receive_addr));
+        out.push_str(&format!("send_addr={}\n", self.send_addr));
+        out.push_str(&format!("heartbeat_ms={}\n", self.heartbeat_interval.as_millis()));
+        if let Some(addr) = self.advertise_addr {
+            out.push_str(&format!("advertise_addr={addr}\n"));
+        }
+        std::fs::write(path, out)
+    }
+}
+
+/// Whether a UDP address is currently free to bind.
+fn port_is_free(addr: SocketAddr) -> bool {
+    UdpSocket::bind(addr).is_ok()
+}
+
+fn prompt(label: &str, default: &str) -> io::Result<String> {
+    print!("{label} [{default}]: ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    let trimmed = line.trim();
+    Ok(if trimmed.is_empty() { default.to_string() } else { trimmed.to_string() })
+}
+
+/// Interactive first-run wizard: prompts for each field with sensible
+/// defaults, validates that the receive port is actually free, and
+/// returns the resulting config for the caller to [`Config::save`].
+pub fn run_setup_wizard(defaults: &Config) -> io::Result<Config> {
+    println!("scalatrix OSC setup");
+    println!("===================");
+
+    let receive_addr = loop {
+        let input = prompt("Receive bind address", &defaults.receive_addr.to_string())?;
+        match input.parse::<SocketAddr>() {
+            Ok(addr) if port_is_free(addr) => break addr,
+            Ok(_) => println!("  that address is already in use — try another"),
+            Err(_) => println!("  couldn't parse that as host:port"),
+        }
+    };
+
+    let send_addr = loop {
+        let input = prompt("Send/target address", &defaults.send_addr.to_string())?;
+        match input.parse::<SocketAddr>() {
+            Ok(addr) => break addr,
+            Err(_) => println!("  couldn't parse that as host:port"),
+        }
+    };
+
+    let heartbeat_interval = loop {
+        let input = prompt("Heartbeat interval (ms)", &defaults.heartbeat_interval.as_millis().to_string())?;
+        match input.parse::<u64>() {
+            Ok(ms) => break Duration::from_millis(ms),
+            Err(_) => println!("  not a number"),
+        }
+    };
+
+    let advertise_default = defaults.advertise_addr.map(|a| a.to_string()).unwrap_or_default();
+    let advertise_input = prompt("Advertise address (blank = same as receive)", &advertise_default)?;
+    let advertise_addr = if advertise_input.is_empty() {
+        None
+    } else {
+        match advertise_input.parse() {
+            Ok(addr) => Some(addr),
+            Err(_) => {
+                println!("  couldn't parse that, leaving unset");
+                None
+            }
+        }
+    };
+
+    Ok(Config { receive_addr, send_addr, heartbeat_interval, advertise_addr })
+}
@@ -0,0 +1,162 @@
+//! OSC transport for distributing tuning/mapping state to PitchGrid
+//! peers.
+//!
+//! Mirrors the Ingen-style split between a receiving [`Dispatcher`],
+//! which routes incoming messages by address pattern, and a
+//! client-side [`Sender`] for outgoing messages. The `osc-receiver`
+//! example hand-matches `msg.addr.as_str()` against a handful of
+//! literal strings in `main()`; this module makes that routing
+//! reusable and testable.
+
+mod config;
+mod pattern;
+pub mod reactor;
+mod transport;
+
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::Mutex;
+
+use rosc::{OscMessage, OscPacket, OscType};
+
+pub use config::{run_setup_wizard, Config};
+pub use pattern::pattern_matches;
+pub use transport::{MulticastConfig, UdpSocketBuilder};
+
+/// Anything an [`OscPacket`] reply can be sent out over: implemented
+/// for [`std::net::UdpSocket`] and, by [`reactor`], for
+/// `mio::net::UdpSocket`, so a [`Dispatcher`] can sit behind either a
+/// blocking socket or the mio reactor without caring which.
+pub trait OscSocket {
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> std::io::Result<usize>;
+}
+
+impl OscSocket for UdpSocket {
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> std::io::Result<usize> {
+        UdpSocket::send_to(self, buf, addr)
+    }
+}
+
+/// A handler registered against an OSC address pattern.
+pub type Handler = Box<dyn Fn(&OscMessage, &Responder) + Send + Sync>;
+
+/// Passed to a [`Handler`] so it can reply to the message's sender
+/// without needing to know which socket it arrived on.
+pub struct Responder<'a> {
+    socket: &'a dyn OscSocket,
+    from: SocketAddr,
+}
+
+impl<'a> Responder<'a> {
+    /// Address the message arrived from.
+    pub fn from(&self) -> SocketAddr {
+        self.from
+    }
+
+    /// Send a reply to the sender of the message being handled.
+    pub fn reply(&self, addr: &str, args: Vec<OscType>) -> std::io::Result<usize> {
+        let packet = OscPacket::Message(OscMessage { addr: addr.to_string(), args });
+        let bytes = rosc::encoder::encode(&packet)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        self.socket.send_to(&bytes, self.from)
+    }
+}
+
+/// Routes incoming [`OscPacket`]s to handlers registered by address
+/// pattern, unpacking [`rosc::OscBundle`]s recursively.
+///
+/// Also implements the PitchGrid query/response convention: a message
+/// arriving at `/pitchgrid/query/tuning` gets an immediate reply with
+/// the current tuning arguments (set via
+/// [`Dispatcher::set_current_tuning`]), so external tools can poll
+/// state instead of only receiving it on change.
+pub struct Dispatcher {
+    routes: Vec<(String, Handler)>,
+    current_tuning: Mutex<Option<Vec<OscType>>>,
+}
+
+impl Default for Dispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self { routes: Vec::new(), current_tuning: Mutex::new(None) }
+    }
+
+    /// Register a handler against an OSC address pattern (e.g.
+    /// `/pitchgrid/plugin/*` or `/pitchgrid/?uning`). See
+    /// [`pattern_matches`] for the supported pattern syntax.
+    pub fn register(
+        &mut self,
+        pattern: impl Into<String>,
+        handler: impl Fn(&OscMessage, &Responder) + Send + Sync + 'static,
+    ) {
+        self.routes.push((pattern.into(), Box::new(handler)));
+    }
+
+    /// Record the current tuning arguments so that
+    /// `/pitchgrid/query/tuning` can answer late-joining clients
+    /// without waiting for the next change.
+    pub fn set_current_tuning(&self, args: Vec<OscType>) {
+        *self.current_tuning.lock().unwrap() = Some(args);
+    }
+
+    /// Dispatch a decoded packet, recursing into bundles.
+    pub fn dispatch(&self, packet: &OscPacket, from: SocketAddr, socket: &dyn OscSocket) {
+        match packet {
+            OscPacket::Message(msg) => self.dispatch_message(msg, from, socket),
+            OscPacket::Bundle(bundle) => {
+                for inner in &bundle.content {
+                    self.dispatch(inner, from, socket);
+                }
+            }
+        }
+    }
+
+    fn dispatch_message(&self, msg: &OscMessage, from: SocketAddr, socket: &dyn OscSocket) {
+        let responder = Responder { socket, from };
+
+        if msg.addr == "/pitchgrid/query/tuning" {
+            let tuning = self.current_tuning.lock().unwrap().clone();
+            if let Some(args) = tuning {
+                let _ = responder.reply("/pitchgrid/plugin/tuning", args);
+            }
+            return;
+        }
+
+        for (pattern, handler) in &self.routes {
+            if pattern_matches(pattern, &msg.addr) {
+                handler(msg, &responder);
+            }
+        }
+    }
+}
+
+/// Client-side counterpart to [`Dispatcher`]: sends OSC messages to a
+/// fixed target address.
+pub struct Sender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl Sender {
+    pub fn new(socket: UdpSocket, target: SocketAddr) -> Self {
+        Self { socket, target }
+    }
+
+    /// Send a message to the configured target.
+    pub fn send(&self, addr: &str, args: Vec<OscType>) -> std::io::Result<usize> {
+        let packet = OscPacket::Message(OscMessage { addr: addr.to_string(), args });
+        let bytes = rosc::encoder::encode(&packet)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        self.socket.send_to(&bytes, self.target)
+    }
+
+    /// The underlying socket, e.g. to pass to [`Dispatcher::dispatch`]
+    /// as the socket replies go out on.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+}
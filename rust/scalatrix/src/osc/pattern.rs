@@ -0,0 +1,86 @@
+//! OSC address-pattern matching, per the OSC 1.0 spec: `?` matches any
+//! single character, `*` matches any run (including empty), `[a-z]` /
+//! `[!...]` character classes, and `{foo,bar}` alternation. Patterns are
+//! compared against the slash-delimited address one segment at a time.
+
+/// Does `address` match `pattern`?
+pub fn pattern_matches(pattern: &str, address: &str) -> bool {
+    let pattern_segs: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+    let address_segs: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+
+    pattern_segs.len() == address_segs.len()
+        && pattern_segs
+            .iter()
+            .zip(address_segs.iter())
+            .all(|(p, a)| {
+                let pattern: Vec<char> = p.chars().collect();
+                let value: Vec<char> = a.chars().collect();
+                segment_matches(&pattern, &value)
+            })
+}
+
+fn segment_matches(pattern: &[char], value: &[char]) -> bool {
+    match pattern.first() {
+        None => value.is_empty(),
+
+        Some('*') => {
+            (0..=value.len()).any(|i| segment_matches(&pattern[1..], &value[i..]))
+        }
+
+        Some('?') => !value.is_empty() && segment_matches(&pattern[1..], &value[1..]),
+
+        Some('[') => match pattern.iter().position(|&c| c == ']') {
+            None => false, // malformed pattern: literal '[' can never match
+            Some(close) => {
+                if value.is_empty() {
+                    return false;
+                }
+                let (negate, class) = match pattern.get(1) {
+                    Some('!') => (true, &pattern[2..close]),
+                    _ => (false, &pattern[1..close]),
+                };
+                let in_class = char_class_matches(class, value[0]);
+                in_class != negate && segment_matches(&pattern[close + 1..], &value[1..])
+            }
+        },
+
+        Some('{') => match pattern.iter().position(|&c| c == '}') {
+            None => false, // malformed pattern: literal '{' can never match
+            Some(close) => {
+                let rest = &pattern[close + 1..];
+                let alternatives: String = pattern[1..close].iter().collect();
+                alternatives.split(',').any(|alt| {
+                    let alt: Vec<char> = alt.chars().collect();
+                    value.len() >= alt.len()
+                        && value[..alt.len()] == alt[..]
+                        && segment_matches(rest, &value[alt.len()..])
+                })
+            }
+        },
+
+        Some(&literal) => {
+            !value.is_empty() && value[0] == literal && segment_matches(&pattern[1..], &value[1..])
+        }
+    }
+}
+
+/// Matches a single character against the contents of an OSC `[...]`
+/// character class (already stripped of brackets and any leading `!`),
+/// which may mix literal characters and `a-z`-style ranges.
+fn char_class_matches(class: &[char], c: char) -> bool {
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            if c >= class[i] && c <= class[i + 2] {
+                return true;
+            }
+            i += 3;
+        } else {
+            if class[i] == c {
+                return true;
+            }
+            i += 1;
+        }
+    }
+    false
+}
@@ -0,0 +1,167 @@
+//! Non-blocking reactor multiplexing an OSC receive socket, a heartbeat
+//! deadline, and a shutdown/control channel on one mio `Poll`.
+//!
+//! Unlike the `osc-receiver` example's blocking socket with a 500 ms
+//! read timeout (which coupled heartbeat latency to the socket
+//! timeout): the heartbeat here is driven by a deadline passed directly
+//! as the `poll()` timeout, so there's no busy spinning, and a
+//! `mio::Waker`-backed [`ReactorHandle`] lets another thread inject a
+//! graceful-stop or force-resend-tuning event from outside the loop.
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use mio::net::UdpSocket as MioUdpSocket;
+use mio::{Events, Interest, Poll, Token, Waker};
+
+use super::{Dispatcher, OscSocket, UdpSocketBuilder};
+
+const SOCKET_TOKEN: Token = Token(0);
+const WAKER_TOKEN: Token = Token(1);
+
+impl OscSocket for MioUdpSocket {
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        MioUdpSocket::send_to(self, buf, addr)
+    }
+}
+
+/// A control event injected into a running [`Reactor`] from another
+/// thread via [`ReactorHandle`].
+pub enum ControlEvent {
+    /// Stop the event loop as soon as this event is taken from the
+    /// queue; control events queued behind it are not processed.
+    Shutdown,
+    /// Ask the caller to resend the current tuning immediately, as if
+    /// a heartbeat had just fired.
+    ResendTuning,
+}
+
+/// A cheaply cloneable handle that can wake a running [`Reactor`] from
+/// another thread.
+#[derive(Clone)]
+pub struct ReactorHandle {
+    waker: Arc<Waker>,
+    queue: Arc<Mutex<VecDeque<ControlEvent>>>,
+}
+
+impl ReactorHandle {
+    fn send(&self, event: ControlEvent) -> io::Result<()> {
+        self.queue.lock().unwrap().push_back(event);
+        self.waker.wake()
+    }
+
+    /// Ask the reactor to stop its event loop.
+    pub fn shutdown(&self) -> io::Result<()> {
+        self.send(ControlEvent::Shutdown)
+    }
+
+    /// Ask the reactor to resend the current tuning immediately.
+    pub fn resend_tuning(&self) -> io::Result<()> {
+        self.send(ControlEvent::ResendTuning)
+    }
+}
+
+/// Owns the event loop: waits on the receive socket, the heartbeat
+/// deadline, and the control waker together, and dispatches decoded
+/// packets into a [`Dispatcher`].
+pub struct Reactor {
+    poll: Poll,
+    socket: MioUdpSocket,
+    dispatcher: Dispatcher,
+    heartbeat_interval: Duration,
+    control_queue: Arc<Mutex<VecDeque<ControlEvent>>>,
+}
+
+impl Reactor {
+    /// Bind the receive socket and construct a reactor plus the handle
+    /// used to control it from other threads.
+    pub fn new(
+        bind_addr: SocketAddr,
+        dispatcher: Dispatcher,
+        heartbeat_interval: Duration,
+    ) -> io::Result<(Self, ReactorHandle)> {
+        Self::with_transport(UdpSocketBuilder::new(bind_addr), dispatcher, heartbeat_interval)
+    }
+
+    /// Like [`Reactor::new`], but bind through a [`UdpSocketBuilder`]
+    /// (e.g. with `SO_REUSEPORT` or a multicast group configured). The
+    /// rest of the event loop — and the `Dispatcher` routing — is
+    /// identical either way.
+    pub fn with_transport(
+        transport: UdpSocketBuilder,
+        dispatcher: Dispatcher,
+        heartbeat_interval: Duration,
+    ) -> io::Result<(Self, ReactorHandle)> {
+        let mut socket = MioUdpSocket::from_std(transport.build()?);
+        let poll = Poll::new()?;
+        poll.registry().register(&mut socket, SOCKET_TOKEN, Interest::READABLE)?;
+        let waker = Arc::new(Waker::new(poll.registry(), WAKER_TOKEN)?);
+        let control_queue = Arc::new(Mutex::new(VecDeque::new()));
+
+        let handle = ReactorHandle { waker, queue: control_queue.clone() };
+        let reactor = Self { poll, socket, dispatcher, heartbeat_interval, control_queue };
+        Ok((reactor, handle))
+    }
+
+    /// Run the event loop until a [`ReactorHandle::shutdown`] fires.
+    /// `on_heartbeat` is called once per heartbeat interval, and again
+    /// immediately whenever a [`ControlEvent::ResendTuning`] arrives.
+    pub fn run(&mut self, mut on_heartbeat: impl FnMut(&MioUdpSocket)) -> io::Result<()> {
+        let mut events = Events::with_capacity(128);
+        let mut buf = [0u8; 2048];
+        let mut next_heartbeat = Instant::now() + self.heartbeat_interval;
+
+        loop {
+            let timeout = next_heartbeat.saturating_duration_since(Instant::now());
+            self.poll.poll(&mut events, Some(timeout))?;
+
+            for event in &events {
+                match event.token() {
+                    SOCKET_TOKEN => self.drain_socket(&mut buf)?,
+                    WAKER_TOKEN => {
+                        if self.drain_control_queue(&mut on_heartbeat) {
+                            return Ok(());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            // Checked on every wakeup, so socket traffic can't hold the
+            // heartbeat back past its deadline.
+            if Instant::now() >= next_heartbeat {
+                on_heartbeat(&self.socket);
+                next_heartbeat = Instant::now() + self.heartbeat_interval;
+            }
+        }
+    }
+
+    fn drain_socket(&self, buf: &mut [u8]) -> io::Result<()> {
+        loop {
+            match self.socket.recv_from(buf) {
+                Ok((size, from)) => {
+                    if let Ok((_, packet)) = rosc::decoder::decode_udp(&buf[..size]) {
+                        self.dispatcher.dispatch(&packet, from, &self.socket);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns `true` once a shutdown event has been processed.
+    fn drain_control_queue(&self, on_heartbeat: &mut impl FnMut(&MioUdpSocket)) -> bool {
+        let mut queue = self.control_queue.lock().unwrap();
+        while let Some(event) = queue.pop_front() {
+            match event {
+                ControlEvent::Shutdown => return true,
+                ControlEvent::ResendTuning => on_heartbeat(&self.socket),
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,101 @@
+//! Socket construction for distributing tuning to a LAN of instruments:
+//! `SO_REUSEPORT` so several receivers on one host can share a port, and
+//! UDP multicast so one PitchGrid source can retune every synth on the
+//! subnet at once instead of sending to a single target.
+
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Multicast group to join, mirrored into a [`UdpSocketBuilder`].
+#[derive(Debug, Clone, Copy)]
+pub struct MulticastConfig {
+    /// Multicast group address to join (e.g. `239.0.0.1`).
+    pub group_addr: IpAddr,
+    /// Local interface address to join an IPv4 group on. Ignored for an
+    /// IPv6 group, which is joined on the default interface.
+    pub interface: IpAddr,
+    /// Outgoing multicast TTL/hop limit.
+    pub ttl: u32,
+    /// Whether to also set `SO_REUSEPORT`, so other receivers on this
+    /// host can join the same group/port.
+    pub reuse_port: bool,
+}
+
+/// Builds a UDP socket for unicast or multicast distribution, exposing
+/// `SO_REUSEPORT` so several receivers on the same host can bind the
+/// identical port.
+pub struct UdpSocketBuilder {
+    bind_addr: SocketAddr,
+    reuse_port: bool,
+    multicast: Option<MulticastConfig>,
+}
+
+impl UdpSocketBuilder {
+    pub fn new(bind_addr: SocketAddr) -> Self {
+        Self { bind_addr, reuse_port: false, multicast: None }
+    }
+
+    /// Enable or disable `SO_REUSEPORT` on the bound socket.
+    pub fn set_reuseport(mut self, enable: bool) -> Self {
+        self.reuse_port = enable;
+        self
+    }
+
+    /// Whether `SO_REUSEPORT` will be set on build.
+    pub fn get_reuseport(&self) -> bool {
+        self.reuse_port
+    }
+
+    /// Join a multicast group after binding.
+    pub fn multicast(mut self, config: MulticastConfig) -> Self {
+        self.reuse_port = self.reuse_port || config.reuse_port;
+        self.multicast = Some(config);
+        self
+    }
+
+    /// Build the socket: set `SO_REUSEADDR`/`SO_REUSEPORT`, bind, join
+    /// any configured multicast group, and hand back a standard
+    /// non-blocking [`std::net::UdpSocket`] ready to be wrapped by
+    /// `mio::net::UdpSocket::from_std`.
+    pub fn build(self) -> io::Result<std::net::UdpSocket> {
+        let domain = if self.bind_addr.is_ipv4() { Domain::IPV4 } else { Domain::IPV6 };
+        let socket = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))?;
+
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        if self.reuse_port {
+            socket.set_reuse_port(true)?;
+        }
+
+        socket.bind(&self.bind_addr.into())?;
+
+        if let Some(mc) = &self.multicast {
+            join_multicast(&socket, mc)?;
+        }
+
+        socket.set_nonblocking(true)?;
+        Ok(socket.into())
+    }
+}
+
+fn join_multicast(socket: &Socket, config: &MulticastConfig) -> io::Result<()> {
+    match (config.group_addr, config.interface) {
+        (IpAddr::V4(group), IpAddr::V4(interface)) => {
+            socket.join_multicast_v4(&group, &interface)?;
+            socket.set_multicast_ttl_v4(config.ttl)?;
+        }
+        (IpAddr::V6(group), _) => {
+            socket.join_multicast_v6(&group, 0)?;
+            socket.set_multicast_hops_v6(config.ttl)?;
+        }
+        (IpAddr::V4(_), IpAddr::V6(_)) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "multicast group and interface address families must match",
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Shared tuning/mapping parameters carried across every PitchGrid
+//! transport — OSC, MQTT, and the protobuf wire format.
+
+use rosc::OscType;
+use serde::{Deserialize, Serialize};
+
+use crate::{Mos, Scale};
+
+/// Tuning/mapping parameters as sent by the PitchGrid plugin: `(mode,
+/// root_freq, stretch, skew, mode_offset, steps, mos_a, mos_b)`.
+///
+/// Used both as the payload of `/pitchgrid/plugin/tuning` and
+/// `/pitchgrid/plugin/mapping` OSC messages, and as the JSON payload
+/// published to `pitchgrid/tuning` over [`crate::bridge::mqtt`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PitchGridParams {
+    pub mode: i32,
+    pub root_freq: f64,
+    pub stretch: f64,
+    pub skew: f64,
+    pub mode_offset: i32,
+    pub steps: i32,
+    pub mos_a: i32,
+    pub mos_b: i32,
+}
+
+impl PitchGridParams {
+    /// Try to parse from OSC args: (i32, f32, f32, f32, i32, i32, i32, i32)
+    pub fn from_osc_args(args: &[OscType]) -> Option<Self> {
+        if args.len() < 8 {
+            return None;
+        }
+        Some(Self {
+            mode: args[0].clone().int()?,
+            root_freq: args[1].clone().float()? as f64,
+            stretch: args[2].clone().float()? as f64,
+            skew: args[3].clone().float()? as f64,
+            mode_offset: args[4].clone().int()?,
+            steps: args[5].clone().int()?,
+            mos_a: args[6].clone().int()?,
+            mos_b: args[7].clone().int()?,
+        })
+    }
+
+    /// Build the MOS these parameters describe.
+    pub fn to_mos(&self) -> Mos {
+        Mos::from_params(self.mos_a, self.mos_b, self.mode, self.stretch, self.skew, 1)
+    }
+
+    /// Run the full mapping pipeline: `Mos::from_params` →
+    /// `generate_mapped_scale`, using the conventional MIDI range
+    /// (128 nodes, root at index 60).
+    pub fn to_scale(&self) -> Scale {
+        self.to_mos().generate_mapped_scale(self.steps, self.mode_offset as f64, self.root_freq, 128, 60)
+    }
+}
@@ -0,0 +1,142 @@
+//! Real-time pitch quantization over a generated [`Scale`](crate::Scale).
+
+use crate::{Node, Scale};
+
+/// Snaps an arbitrary input pitch to the nearest active node of a
+/// [`Scale`](crate::Scale), for live pitch-correction and sequencer use.
+///
+/// Built once via [`Scale::quantizer`]; the lookup table is sorted by
+/// pitch up front so `quantize` is a binary search rather than a linear
+/// scan over every call.
+pub struct Quantizer {
+    /// Sorted by log2 pitch: (log2 pitch, node, original scale index).
+    sorted: Vec<(f64, Node, usize)>,
+    /// Maps original scale index -> position in `sorted`.
+    index_to_sorted: Vec<usize>,
+    /// Per-degree enable mask, indexed `scale_index % degree_mask.len()`.
+    /// Empty means every degree is enabled.
+    degree_mask: Vec<bool>,
+}
+
+impl Quantizer {
+    pub(crate) fn new(scale: &Scale) -> Self {
+        let nodes = scale.nodes();
+        let mut sorted: Vec<(f64, Node, usize)> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.pitch.log2(), *node, i))
+            .collect();
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut index_to_sorted = vec![0usize; nodes.len()];
+        for (pos, &(_, _, original_index)) in sorted.iter().enumerate() {
+            index_to_sorted[original_index] = pos;
+        }
+
+        Self { sorted, index_to_sorted, degree_mask: Vec::new() }
+    }
+
+    /// Enable or disable individual scale degrees (indexed `mod
+    /// degree_mask.len()`), analogous to a hardware quantizer's
+    /// per-note key enable. Pass an empty slice to re-enable everything.
+    pub fn set_degree_mask(&mut self, mask: &[bool]) {
+        self.degree_mask = mask.to_vec();
+    }
+
+    fn is_masked(&self, sorted_pos: usize) -> bool {
+        if self.degree_mask.is_empty() {
+            return false;
+        }
+        let (_, _, original_index) = self.sorted[sorted_pos];
+        !self.degree_mask[original_index % self.degree_mask.len()]
+    }
+
+    /// Find the nearest unmasked node to `target` (a log2 pitch),
+    /// returning its position in `sorted` and the correction in cents.
+    fn nearest_unmasked(&self, target: f64) -> (usize, f64) {
+        assert!(!self.sorted.is_empty(), "quantizer has no nodes");
+
+        let pos = self.sorted.partition_point(|e| e.0 < target);
+
+        let mut lower = if pos > 0 { Some(pos - 1) } else { None };
+        while let Some(i) = lower {
+            if self.is_masked(i) {
+                lower = if i > 0 { Some(i - 1) } else { None };
+            } else {
+                break;
+            }
+        }
+
+        let mut upper = if pos < self.sorted.len() { Some(pos) } else { None };
+        while let Some(i) = upper {
+            if self.is_masked(i) {
+                upper = if i + 1 < self.sorted.len() { Some(i + 1) } else { None };
+            } else {
+                break;
+            }
+        }
+
+        let chosen = match (lower, upper) {
+            (Some(l), Some(u)) => {
+                let below = target - self.sorted[l].0;
+                let above = self.sorted[u].0 - target;
+                if below <= above { l } else { u }
+            }
+            (Some(l), None) => l,
+            (None, Some(u)) => u,
+            (None, None) => panic!("quantizer: every degree is masked"),
+        };
+
+        let cents = (target - self.sorted[chosen].0) * 1200.0;
+        (chosen, cents)
+    }
+
+    /// Snap `freq` (Hz) to the nearest active scale node, returning the
+    /// node and the correction applied in cents (positive = `freq` was
+    /// sharp of the node).
+    pub fn quantize(&self, freq: f64) -> (Node, f64) {
+        let (pos, cents) = self.nearest_unmasked(freq.log2());
+        (self.sorted[pos].1, cents)
+    }
+
+    /// Like [`Quantizer::quantize`], but only switches away from the
+    /// previously held node (`prev_index`, its index in the scale, e.g.
+    /// looked up from its `natural_coord` via [`Scale::coord_to_index`])
+    /// once `freq` moves more than
+    /// `cents_margin` past the midpoint between it and the nearest
+    /// alternative. Prevents jitter when a slow input hovers between two
+    /// degrees.
+    pub fn quantize_with_hysteresis(&self, freq: f64, prev_index: usize, cents_margin: f64) -> (Node, f64) {
+        let target = freq.log2();
+        let (chosen_pos, _) = self.nearest_unmasked(target);
+
+        let prev_pos = self
+            .index_to_sorted
+            .get(prev_index)
+            .copied()
+            .filter(|&p| !self.is_masked(p));
+
+        let Some(prev_pos) = prev_pos else {
+            let (log2_pitch, node, _) = self.sorted[chosen_pos];
+            return (node, (target - log2_pitch) * 1200.0);
+        };
+
+        let settled = if prev_pos == chosen_pos {
+            prev_pos
+        } else {
+            let prev_log2 = self.sorted[prev_pos].0;
+            let chosen_log2 = self.sorted[chosen_pos].0;
+            let mid = (prev_log2 + chosen_log2) / 2.0;
+            let margin = cents_margin / 1200.0;
+            let crossed = if chosen_log2 > prev_log2 {
+                target > mid + margin
+            } else {
+                target < mid - margin
+            };
+            if crossed { chosen_pos } else { prev_pos }
+        };
+
+        let (log2_pitch, node, _) = self.sorted[settled];
+        (node, (target - log2_pitch) * 1200.0)
+    }
+}
@@ -0,0 +1,153 @@
+//! Schema-versioned protobuf wire format, shared by the C++ plugin and
+//! every Rust consumer instead of the eight positional OSC arguments
+//! `(mode, root_freq, stretch, skew, mode_offset, steps, mos_a, mos_b)`
+//! — a format where adding a field silently breaks every existing
+//! parser.
+//!
+//! Generated from `proto/tuning.proto` by `build.rs`; see
+//! [`TuningParams`] and [`MappedScale`].
+//!
+//! `Scale` only encodes ([`Scale::to_proto_bytes`]) and has no decode
+//! counterpart: it's a thin handle onto an FFI-owned C++ object and
+//! can't be reconstructed from raw wire bytes. [`DecodedScale::from_proto_bytes`]
+//! decodes a `MappedScale` message into a plain, owned Rust struct
+//! instead, for consumers that just need
+//! the node/coord/pitch data rather than a live `Scale`.
+
+mod generated {
+    #![allow(clippy::all)]
+    include!(concat!(env!("OUT_DIR"), "/tuning.rs"));
+}
+pub use generated::{MappedNode, MappedScale, TuningParams};
+
+use protobuf::Message;
+use rosc::OscType;
+
+use crate::{Node, PitchGridParams, Scale};
+
+/// Current schema version written by this crate. Readers should accept
+/// any version they understand and reject (or gracefully degrade for)
+/// anything newer.
+pub const SCHEMA_VERSION: u32 = 1;
+
+impl PitchGridParams {
+    /// Encode as a schema-versioned `TuningParams` protobuf message.
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        let mut msg = TuningParams::new();
+        msg.set_schema_version(SCHEMA_VERSION);
+        msg.set_mode(self.mode);
+        msg.set_root_freq(self.root_freq);
+        msg.set_stretch(self.stretch);
+        msg.set_skew(self.skew);
+        msg.set_mode_offset(self.mode_offset);
+        msg.set_steps(self.steps);
+        msg.set_mos_a(self.mos_a);
+        msg.set_mos_b(self.mos_b);
+        msg.write_to_bytes().expect("TuningParams always encodes")
+    }
+
+    /// Decode a `TuningParams` protobuf message.
+    pub fn from_proto_bytes(bytes: &[u8]) -> protobuf::ProtobufResult<Self> {
+        let msg = TuningParams::parse_from_bytes(bytes)?;
+        Ok(Self {
+            mode: msg.get_mode(),
+            root_freq: msg.get_root_freq(),
+            stretch: msg.get_stretch(),
+            skew: msg.get_skew(),
+            mode_offset: msg.get_mode_offset(),
+            steps: msg.get_steps(),
+            mos_a: msg.get_mos_a(),
+            mos_b: msg.get_mos_b(),
+        })
+    }
+}
+
+impl Scale {
+    /// Encode as a schema-versioned `MappedScale` protobuf message.
+    /// `mos` supplies the in-scale flag for each node (via
+    /// [`crate::Mos::node_in_scale`]), since a `Scale` on its own
+    /// doesn't retain the MOS structure it was generated from.
+    pub fn to_proto_bytes(&self, mos: &crate::Mos) -> Vec<u8> {
+        let mut msg = MappedScale::new();
+        msg.set_schema_version(SCHEMA_VERSION);
+        msg.set_root_idx(self.root_idx() as u32);
+        msg.set_base_freq(self.base_freq());
+
+        let nodes = self
+            .nodes()
+            .iter()
+            .map(|node: &Node| {
+                let mut wire_node = MappedNode::new();
+                wire_node.set_x(node.natural_coord.x);
+                wire_node.set_y(node.natural_coord.y);
+                wire_node.set_pitch(node.pitch);
+                wire_node.set_in_scale(mos.node_in_scale(node.natural_coord));
+                wire_node
+            })
+            .collect();
+        msg.set_nodes(nodes);
+
+        msg.write_to_bytes().expect("MappedScale always encodes")
+    }
+}
+
+/// One node of a [`DecodedScale`], decoded from a [`MappedNode`] wire
+/// message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecodedNode {
+    pub x: i32,
+    pub y: i32,
+    pub pitch: f64,
+    pub in_scale: bool,
+}
+
+/// A decoded [`MappedScale`]: the plain, owned counterpart to
+/// [`Scale`] that a consumer without access to the C++ FFI core (or
+/// the originating [`crate::Mos`]) can reconstruct from wire bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedScale {
+    pub schema_version: u32,
+    pub nodes: Vec<DecodedNode>,
+    pub root_idx: u32,
+    pub base_freq: f64,
+}
+
+impl DecodedScale {
+    /// Decode a `MappedScale` protobuf message.
+    pub fn from_proto_bytes(bytes: &[u8]) -> protobuf::ProtobufResult<Self> {
+        let msg = MappedScale::parse_from_bytes(bytes)?;
+        let nodes = msg
+            .get_nodes()
+            .iter()
+            .map(|node| DecodedNode {
+                x: node.get_x(),
+                y: node.get_y(),
+                pitch: node.get_pitch(),
+                in_scale: node.get_in_scale(),
+            })
+            .collect();
+
+        Ok(Self {
+            schema_version: msg.get_schema_version(),
+            nodes,
+            root_idx: msg.get_root_idx(),
+            base_freq: msg.get_base_freq(),
+        })
+    }
+}
+
+/// Wrap an already-encoded protobuf message as an OSC blob argument, so
+/// it can ride over the existing `/pitchgrid/plugin/*` addresses as a
+/// single typed argument instead of a positional tuple.
+pub fn to_osc_blob(bytes: Vec<u8>) -> OscType {
+    OscType::Blob(bytes)
+}
+
+/// The inverse of [`to_osc_blob`]: extract the raw bytes back out of an
+/// OSC blob argument.
+pub fn from_osc_blob(arg: &OscType) -> Option<&[u8]> {
+    match arg {
+        OscType::Blob(bytes) => Some(bytes),
+        _ => None,
+    }
+}